@@ -0,0 +1,130 @@
+use serde::Serialize;
+
+/// Severity of a [`Diagnostic`] raised while parsing or validating a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Warn,
+    Deprecation,
+    Help,
+}
+
+/// A single, machine-readable note about a field in `wrangler.toml`.
+///
+/// `field_path` is empty for top-level fields, or e.g. `["env", "staging", "route"]`
+/// for a field nested under `[env.staging]`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub field_path: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn warn(message: impl Into<String>, field_path: Vec<String>) -> Self {
+        Diagnostic {
+            level: Level::Warn,
+            message: message.into(),
+            field_path,
+        }
+    }
+
+    pub fn deprecation(message: impl Into<String>, field_path: Vec<String>) -> Self {
+        Diagnostic {
+            level: Level::Deprecation,
+            message: message.into(),
+            field_path,
+        }
+    }
+
+    pub fn help(message: impl Into<String>, field_path: Vec<String>) -> Self {
+        Diagnostic {
+            level: Level::Help,
+            message: message.into(),
+            field_path,
+        }
+    }
+
+    fn path_string(&self) -> String {
+        if self.field_path.is_empty() {
+            "top-level".to_string()
+        } else {
+            self.field_path.join(".")
+        }
+    }
+}
+
+/// An ordered collection of [`Diagnostic`]s accumulated while reading a manifest.
+///
+/// Mirrors Cargo's `Warnings`: instead of printing as soon as a questionable field is
+/// encountered, callers push onto this collector and decide later how (or whether) to
+/// render it, so CI can ask for `--diagnostics-format json` instead of scraping stdout.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.0.push(diagnostic);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0.iter()
+    }
+
+    /// Renders every diagnostic via `crate::terminal::message` as
+    /// `[<field path>] <message>`, where the field path is `top-level` or the
+    /// dot-joined `field_path`; `Help` goes through `help`, the other levels through `warn`.
+    pub fn print(&self) {
+        for diagnostic in &self.0 {
+            let rendered = format!("[{}] {}", diagnostic.path_string(), diagnostic.message);
+            match diagnostic.level {
+                Level::Warn => crate::terminal::message::warn(&rendered),
+                Level::Deprecation => crate::terminal::message::warn(&rendered),
+                Level::Help => crate::terminal::message::help(&rendered),
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, failure::Error> {
+        serde_json::to_string_pretty(&self.0).map_err(|e| failure::format_err!("{}", e))
+    }
+}
+
+/// Selects how `Manifest`'s accumulated diagnostics are rendered. It is chosen through
+/// the `CF_DIAGNOSTICS_FORMAT` env var (see [`DiagnosticsFormat::from_env`]); no
+/// command-line flag sets it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagnosticsFormat {
+    Pretty,
+    Json,
+}
+
+impl std::str::FromStr for DiagnosticsFormat {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(DiagnosticsFormat::Pretty),
+            "json" => Ok(DiagnosticsFormat::Json),
+            other => failure::bail!("unknown diagnostics format \"{}\", expected \"pretty\" or \"json\"", other),
+        }
+    }
+}
+
+impl DiagnosticsFormat {
+    /// Reads the format from `CF_DIAGNOSTICS_FORMAT`, the same `CF`-prefixed env var
+    /// convention `read_config` layers in for any other wrangler setting, defaulting
+    /// to `Pretty` when it is unset or not recognised. `Manifest::new` renders through
+    /// this on every parse, so setting `CF_DIAGNOSTICS_FORMAT=json` switches every
+    /// command that parses a manifest to JSON output.
+    pub fn from_env() -> Self {
+        std::env::var("CF_DIAGNOSTICS_FORMAT")
+            .ok()
+            .and_then(|format| format.parse().ok())
+            .unwrap_or(DiagnosticsFormat::Pretty)
+    }
+}
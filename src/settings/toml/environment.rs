@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use serde_with::rust::string_empty_as_none;
+
+use crate::settings::toml::deploy_config::RouteConfig;
+use crate::settings::toml::kv_namespace::KvNamespace;
+use crate::settings::toml::site::Site;
+
+/// A `[env.<name>]` table in `wrangler.toml`, overriding top-level fields for a
+/// named deploy target (e.g. `staging`, `production`).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Environment {
+    pub name: Option<String>,
+    pub account_id: Option<String>,
+    pub workers_dev: Option<bool>,
+    #[serde(default, with = "string_empty_as_none")]
+    pub route: Option<String>,
+    pub routes: Option<Vec<String>>,
+    #[serde(default, with = "string_empty_as_none")]
+    pub zone_id: Option<String>,
+    pub webpack_config: Option<String>,
+    #[serde(rename = "kv-namespaces")]
+    pub kv_namespaces: Option<Vec<KvNamespace>>,
+    pub site: Option<Site>,
+    /// The name of a sibling environment this one inherits unset fields from.
+    ///
+    /// Opt-in, mirroring Cargo's workspace inheritance: a leaf environment like
+    /// `preview` can `extends = "base"` to pull in everything it doesn't set itself,
+    /// including `kv_namespaces`, which otherwise never inherits.
+    pub extends: Option<String>,
+}
+
+impl Environment {
+    /// Builds this environment's route configuration, falling back to the top-level
+    /// `account_id`/`zone_id` when this environment doesn't set its own. Returns `None`
+    /// when this environment has no deploy-target fields of its own, signaling that the
+    /// caller should fall back to the top-level configuration entirely.
+    pub fn route_config(
+        &self,
+        top_level_account_id: String,
+        top_level_zone_id: Option<String>,
+    ) -> Option<RouteConfig> {
+        if self.workers_dev.is_none()
+            && self.route.is_none()
+            && self.routes.is_none()
+            && self.zone_id.is_none()
+        {
+            return None;
+        }
+
+        Some(RouteConfig {
+            account_id: self.account_id.clone().or(Some(top_level_account_id)),
+            workers_dev: self.workers_dev,
+            route: self.route.clone(),
+            routes: self.routes.clone(),
+            zone_id: self.zone_id.clone().or(top_level_zone_id),
+        })
+    }
+}
@@ -11,15 +11,18 @@ use serde_with::rust::string_empty_as_none;
 
 use crate::commands::validate_worker_name;
 use crate::settings::toml::deploy_config::{DeployConfig, RouteConfig};
+use crate::settings::toml::diagnostics::{Diagnostic, DiagnosticsFormat, Diagnostics};
 use crate::settings::toml::environment::Environment;
 use crate::settings::toml::kv_namespace::KvNamespace;
 use crate::settings::toml::site::Site;
+use crate::settings::toml::state::State;
 use crate::settings::toml::target_type::TargetType;
+use crate::settings::toml::workspace::{MaybeWorkspace, Workspace};
 use crate::settings::toml::Target;
 use crate::terminal::emoji;
 use crate::terminal::message;
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Manifest {
     #[serde(default)]
     pub name: String,
@@ -41,28 +44,159 @@ pub struct Manifest {
     #[serde(rename = "kv-namespaces")]
     pub kv_namespaces: Option<Vec<KvNamespace>>,
     pub env: Option<HashMap<String, Environment>>,
+    /// Declares this manifest as a workspace root that member manifests (see
+    /// `RawManifest::account_id` and friends) can inherit shared fields from.
+    pub workspace: Option<Workspace>,
+    /// Accumulated warnings, deprecations, and hints discovered while parsing this
+    /// manifest. Not part of the on-disk format.
+    #[serde(skip)]
+    pub diagnostics: Diagnostics,
+    /// The directory this manifest was loaded from. Used to locate `.wrangler/state.toml`.
+    /// Not part of the on-disk format.
+    #[serde(skip)]
+    pub project_root: PathBuf,
+}
+
+/// The as-written shape of `wrangler.toml`, before workspace-inherited fields are
+/// resolved into the plain values `Manifest` exposes to the rest of wrangler.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct RawManifest {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    target_type: TargetType,
+    #[serde(default)]
+    account_id: MaybeWorkspace<String>,
+    workers_dev: Option<bool>,
+    #[serde(default, with = "string_empty_as_none")]
+    route: Option<String>,
+    routes: Option<Vec<String>>,
+    #[serde(default, with = "string_empty_as_none")]
+    zone_id: Option<String>,
+    webpack_config: Option<String>,
+    private: Option<bool>,
+    site: Option<Site>,
+    #[serde(rename = "kv-namespaces")]
+    kv_namespaces: Option<MaybeWorkspace<Vec<KvNamespace>>>,
+    env: Option<HashMap<String, Environment>>,
+    workspace: Option<Workspace>,
+}
+
+impl RawManifest {
+    /// Resolves every `{ workspace = true }` field against `shared`, producing the
+    /// plain `Manifest` the rest of wrangler operates on. `zone_id` has no
+    /// `{ workspace = true }` form: when the member leaves it unset, it takes
+    /// `shared`'s `zone_id` without any opt-in.
+    fn resolve(self, shared: Option<&crate::settings::toml::workspace::WorkspaceShared>) -> Result<Manifest, failure::Error> {
+        let account_id = self
+            .account_id
+            .resolve("account_id", shared.and_then(|s| s.account_id.as_ref()))?;
+        let kv_namespaces = match &self.kv_namespaces {
+            Some(maybe) => Some(
+                maybe.resolve("kv-namespaces", shared.and_then(|s| s.kv_namespaces.as_ref()))?,
+            ),
+            None => None,
+        };
+        let zone_id = self
+            .zone_id
+            .or_else(|| shared.and_then(|s| s.zone_id.clone()));
+
+        Ok(Manifest {
+            name: self.name,
+            target_type: self.target_type,
+            account_id,
+            workers_dev: self.workers_dev,
+            route: self.route,
+            routes: self.routes,
+            zone_id,
+            webpack_config: self.webpack_config,
+            private: self.private,
+            site: self.site,
+            kv_namespaces,
+            env: self.env,
+            workspace: self.workspace,
+            diagnostics: Diagnostics::default(),
+            project_root: PathBuf::new(),
+        })
+    }
+}
+
+// Diagnostics are derived from parsing, not part of a manifest's on-disk identity, so
+// two manifests with the same fields are equal regardless of what they warned about.
+impl PartialEq for Manifest {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.target_type == other.target_type
+            && self.account_id == other.account_id
+            && self.workers_dev == other.workers_dev
+            && self.route == other.route
+            && self.routes == other.routes
+            && self.zone_id == other.zone_id
+            && self.webpack_config == other.webpack_config
+            && self.private == other.private
+            && self.site == other.site
+            && self.kv_namespaces == other.kv_namespaces
+            && self.env == other.env
+            && self.workspace == other.workspace
+    }
 }
 
 impl Manifest {
     pub fn new(config_path: &Path) -> Result<Self, failure::Error> {
-        let config = read_config(config_path)?;
+        // Found first so its directory can be excluded from the generic ancestor merge
+        // below: a workspace root's shared fields are inherited explicitly through
+        // `RawManifest::resolve`, not by merging every one of its top-level fields in.
+        let workspace_root = find_workspace_root(config_path);
+        let exclude_dir = workspace_root.as_ref().map(|(dir, _)| dir.as_path());
 
-        let manifest: Manifest = match config.try_into() {
+        let config = read_config(config_path, exclude_dir)?;
+
+        let raw_manifest: RawManifest = match config.try_into() {
             Ok(m) => m,
             Err(e) => {
                 if e.to_string().contains("unknown field `kv-namespaces`") {
                     failure::bail!("kv-namespaces should not live under the [site] table in wrangler.toml; please move it above [site].")
                 } else {
-                    failure::bail!(e)
+                    // The `config` crate loses spans by the time it reports an error, so
+                    // re-parse the raw file with `toml` directly to point at the exact
+                    // line and column of the offending key.
+                    let source = fs::read_to_string(config_path).unwrap_or_default();
+                    match toml::from_str::<RawManifest>(&source) {
+                        Err(toml_err) => return Err(located_toml_error(&source, config_path, &toml_err)),
+                        Ok(_) => failure::bail!(e),
+                    }
                 }
             }
         };
 
+        let shared = workspace_root.as_ref().map(|(_, workspace)| &workspace.shared);
+
+        let mut manifest = raw_manifest.resolve(shared)?;
+        manifest.project_root = config_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+
         check_for_duplicate_names(&manifest)?;
 
+        manifest.diagnostics = manifest.collect_diagnostics();
+        manifest.print_diagnostics(DiagnosticsFormat::from_env())?;
+
         Ok(manifest)
     }
 
+    /// Renders this manifest's accumulated [`Diagnostics`] in
+    /// the requested format. Called with [`DiagnosticsFormat::from_env`] on every
+    /// [`Manifest::new`], so setting `CF_DIAGNOSTICS_FORMAT=json` is enough for CI to
+    /// get machine-readable output without needing its own `--diagnostics-format` flag.
+    pub fn print_diagnostics(&self, format: DiagnosticsFormat) -> Result<(), failure::Error> {
+        match format {
+            DiagnosticsFormat::Pretty => self.diagnostics.print(),
+            DiagnosticsFormat::Json => println!("{}", self.diagnostics.to_json()?),
+        }
+        Ok(())
+    }
+
     pub fn generate(
         name: String,
         target_type: Option<TargetType>,
@@ -126,16 +260,106 @@ impl Manifest {
     }
 
     pub fn worker_name(&self, env_arg: Option<&str>) -> String {
-        if let Some(environment) = self.get_environment(env_arg).unwrap_or_default() {
-            if let Some(name) = &environment.name {
-                return name.clone();
+        match self.resolve_environment(env_arg) {
+            Ok((effective_env, environment)) => {
+                Self::format_worker_name(&self.name, effective_env.as_deref(), environment.as_ref())
+            }
+            Err(e) => {
+                log::warn!(
+                    "could not resolve an environment for the worker name, falling back to the top-level name: {}",
+                    e
+                );
+                self.name.clone()
+            }
+        }
+    }
+
+    /// Builds the worker name for an already-resolved environment: its own `name` if
+    /// set, else `<name>-<env>`, else just `name` when there is no environment.
+    fn format_worker_name(
+        name: &str,
+        effective_env: Option<&str>,
+        environment: Option<&Environment>,
+    ) -> String {
+        if let Some(environment) = environment {
+            if let Some(env_name) = &environment.name {
+                return env_name.clone();
             }
-            if let Some(env) = env_arg {
-                return format!("{}-{}", self.name, env);
+            if let Some(env) = effective_env {
+                return format!("{}-{}", name, env);
             }
         }
 
-        self.name.clone()
+        name.to_string()
+    }
+
+    /// Resolves `environment_name` to the environment this command should operate on
+    /// (chasing its `extends` chain), falling back to the active environment persisted
+    /// in `.wrangler/state.toml` when no explicit `--env` is given. Reads
+    /// `.wrangler/state.toml` at most once, returning both the resolved environment
+    /// name and its config so callers don't each re-resolve it independently.
+    fn resolve_environment(
+        &self,
+        environment_name: Option<&str>,
+    ) -> Result<(Option<String>, Option<Environment>), failure::Error> {
+        let environment_name = match environment_name
+            .map(str::to_string)
+            .or_else(|| self.active_environment())
+        {
+            Some(environment_name) => environment_name,
+            None => return Ok((None, None)),
+        };
+
+        let environment_table = self.env.as_ref().ok_or_else(|| {
+            failure::format_err!(
+                "{} There are no environments specified in your wrangler.toml",
+                emoji::WARN
+            )
+        })?;
+
+        let environment = environment_table.get(&environment_name).ok_or_else(|| {
+            failure::format_err!(
+                "{} Could not find environment with name \"{}\"",
+                emoji::WARN,
+                environment_name
+            )
+        })?;
+
+        let mut path = Vec::new();
+        let resolved = resolve_environment_extends(
+            &environment_name,
+            environment,
+            environment_table,
+            &mut path,
+        )?;
+
+        Ok((Some(environment_name), Some(resolved)))
+    }
+
+    /// The environment set via [`set_active_environment`](Self::set_active_environment),
+    /// if any. A manifest without a known `project_root` (built via `FromStr` rather
+    /// than [`Manifest::new`]) has nowhere to look up `.wrangler/state.toml`, so this
+    /// is a no-op rather than silently resolving it relative to the process's CWD.
+    pub fn active_environment(&self) -> Option<String> {
+        if self.project_root.as_os_str().is_empty() {
+            return None;
+        }
+        State::read(&self.project_root)
+            .ok()
+            .and_then(|state| state.current_environment)
+    }
+
+    /// Persists `environment_name` as the active environment, so it no longer needs to
+    /// be passed via `--env`. Validated against `self.env` the same way `--env` is.
+    pub fn set_active_environment(&self, environment_name: &str) -> Result<(), failure::Error> {
+        self.get_environment(Some(environment_name))?;
+        State::set_current_environment(&self.project_root, environment_name)
+    }
+
+    /// Clears the active environment set via
+    /// [`set_active_environment`](Self::set_active_environment).
+    pub fn clear_active_environment(&self) -> Result<(), failure::Error> {
+        State::clear_current_environment(&self.project_root)
     }
 
     fn route_config(&self) -> RouteConfig {
@@ -149,10 +373,11 @@ impl Manifest {
     }
 
     pub fn deploy_config(&self, env: Option<&str>) -> Result<DeployConfig, failure::Error> {
-        let script = self.worker_name(env);
+        let (effective_env, environment) = self.resolve_environment(env)?;
+        let script = Self::format_worker_name(&self.name, effective_env.as_deref(), environment.as_ref());
         validate_worker_name(&script)?;
 
-        if let Some(environment) = self.get_environment(env)? {
+        if let Some(environment) = environment {
             // if there is an environment level deploy target, try to return that
             if let Some(env_route_config) =
                 environment.route_config(self.account_id.clone(), self.zone_id.clone())
@@ -191,10 +416,10 @@ impl Manifest {
             site: self.site.clone(),                   // MUST NOT inherit
         };
 
-        let environment = self.get_environment(environment_name)?;
+        let (effective_env, environment) = self.resolve_environment(environment_name)?;
 
         if let Some(environment) = environment {
-            target.name = self.worker_name(environment_name);
+            target.name = Self::format_worker_name(&self.name, effective_env.as_deref(), Some(&environment));
             if let Some(account_id) = &environment.account_id {
                 target.account_id = account_id.clone();
             }
@@ -208,119 +433,119 @@ impl Manifest {
         Ok(target)
     }
 
+    /// Resolves the named environment, chasing its `extends` chain (if any) and
+    /// overlaying each ancestor's unset fields onto the child.
     pub fn get_environment(
         &self,
         environment_name: Option<&str>,
-    ) -> Result<Option<&Environment>, failure::Error> {
-        // check for user-specified environment name
-        if let Some(environment_name) = environment_name {
-            if let Some(environment_table) = &self.env {
-                if let Some(environment) = environment_table.get(environment_name) {
-                    Ok(Some(environment))
-                } else {
-                    failure::bail!(format!(
-                        "{} Could not find environment with name \"{}\"",
-                        emoji::WARN,
-                        environment_name
-                    ))
-                }
-            } else {
-                failure::bail!(format!(
-                    "{} There are no environments specified in your wrangler.toml",
-                    emoji::WARN
-                ))
-            }
-        } else {
-            Ok(None)
-        }
+    ) -> Result<Option<Environment>, failure::Error> {
+        self.resolve_environment(environment_name)
+            .map(|(_, environment)| environment)
     }
 
     fn warn_on_account_info(&self) {
+        let diagnostics = self.account_info_diagnostics();
+        if !diagnostics.is_empty() {
+            message::help(
+                "You will need to update the following fields in the created wrangler.toml file before continuing:"
+            );
+            message::help(
+                "You can find your account_id and zone_id in the right sidebar of the zone overview tab at https://dash.cloudflare.com"
+            );
+            diagnostics.print();
+        }
+    }
+
+    /// Collects diagnostics for fields that still need filling in (missing account
+    /// info, kv-namespaces without a `namespace_id`, ...), one per top-level or
+    /// `env.<name>` field.
+    fn account_info_diagnostics(&self) -> Diagnostics {
         let account_id_env = env::var("CF_ACCOUNT_ID").is_ok();
         let zone_id_env = env::var("CF_ZONE_ID").is_ok();
-        let mut top_level_fields: Vec<String> = Vec::new();
-        if !account_id_env {
-            top_level_fields.push("account_id".to_string());
+        let mut diagnostics = Diagnostics::default();
+
+        if self.account_id.is_empty() && !account_id_env {
+            diagnostics.push(Diagnostic::warn(
+                "account_id needs to be set",
+                vec!["account_id".to_string()],
+            ));
         }
         if let Some(kv_namespaces) = &self.kv_namespaces {
             for kv_namespace in kv_namespaces {
-                top_level_fields.push(format!(
-                    "kv-namespace {} needs a namespace_id",
-                    kv_namespace.binding
+                diagnostics.push(Diagnostic::warn(
+                    format!(
+                        "kv-namespace {} needs a namespace_id",
+                        kv_namespace.binding
+                    ),
+                    vec!["kv-namespaces".to_string()],
                 ));
             }
         }
         if let Some(route) = &self.route {
             if !route.is_empty() {
-                top_level_fields.push("route".to_string());
+                diagnostics.push(Diagnostic::warn("route needs to be set", vec!["route".to_string()]));
             }
         }
         if let Some(zone_id) = &self.zone_id {
             if !zone_id.is_empty() && !zone_id_env {
-                top_level_fields.push("zone_id".to_string());
+                diagnostics.push(Diagnostic::warn("zone_id needs to be set", vec!["zone_id".to_string()]));
             }
         }
 
-        let mut env_fields: HashMap<String, Vec<String>> = HashMap::new();
-
         if let Some(env) = &self.env {
             for (env_name, env) in env {
-                let mut current_env_fields: Vec<String> = Vec::new();
-                if env.account_id.is_some() && !account_id_env {
-                    current_env_fields.push("account_id".to_string());
+                let path = |field: &str| vec!["env".to_string(), env_name.to_string(), field.to_string()];
+                // An environment without its own `account_id` falls back to the
+                // top-level value (see `get_target`), so the effective value -- not
+                // just whether this environment happens to set it -- is what matters.
+                let account_id_set = env
+                    .account_id
+                    .as_ref()
+                    .map(|id| !id.is_empty())
+                    .unwrap_or_else(|| !self.account_id.is_empty());
+                if !account_id_set && !account_id_env {
+                    diagnostics.push(Diagnostic::warn("account_id needs to be set", path("account_id")));
                 }
                 if let Some(kv_namespaces) = &env.kv_namespaces {
                     for kv_namespace in kv_namespaces {
-                        current_env_fields.push(format!(
-                            "kv-namespace {} needs a namespace_id",
-                            kv_namespace.binding
+                        diagnostics.push(Diagnostic::warn(
+                            format!(
+                                "kv-namespace {} needs a namespace_id",
+                                kv_namespace.binding
+                            ),
+                            path("kv-namespaces"),
                         ));
                     }
                 }
                 if let Some(route) = &env.route {
                     if !route.is_empty() {
-                        current_env_fields.push("route".to_string());
+                        diagnostics.push(Diagnostic::warn("route needs to be set", path("route")));
                     }
                 }
                 if let Some(zone_id) = &env.zone_id {
                     if !zone_id.is_empty() && !zone_id_env {
-                        current_env_fields.push("zone_id".to_string());
+                        diagnostics.push(Diagnostic::warn("zone_id needs to be set", path("zone_id")));
                     }
                 }
-                if !current_env_fields.is_empty() {
-                    env_fields.insert(env_name.to_string(), current_env_fields);
-                }
             }
         }
-        let has_top_level_fields = !top_level_fields.is_empty();
-        let has_env_fields = !env_fields.is_empty();
-        let mut needs_new_line = false;
-        if has_top_level_fields || has_env_fields {
-            message::help(
-                "You will need to update the following fields in the created wrangler.toml file before continuing:"
-            );
-            message::help(
-                "You can find your account_id and zone_id in the right sidebar of the zone overview tab at https://dash.cloudflare.com"
-            );
-            if has_top_level_fields {
-                needs_new_line = true;
-                for top_level_field in top_level_fields {
-                    println!("- {}", top_level_field);
-                }
-            }
-            if has_env_fields {
-                for (env_name, env_fields) in env_fields {
-                    if needs_new_line {
-                        println!();
-                    }
-                    println!("[env.{}]", env_name);
-                    needs_new_line = true;
-                    for env_field in env_fields {
-                        println!("  - {}", env_field);
-                    }
-                }
-            }
+
+        diagnostics
+    }
+
+    /// Collects every diagnostic worth surfacing about this manifest: missing account
+    /// info plus deprecation notices such as the singular `route` field.
+    fn collect_diagnostics(&self) -> Diagnostics {
+        let mut diagnostics = self.account_info_diagnostics();
+
+        if self.route.is_some() {
+            diagnostics.push(Diagnostic::deprecation(
+                "`route` is deprecated in favor of `routes`, which accepts a list of routes",
+                vec!["route".to_string()],
+            ));
         }
+
+        diagnostics
     }
 }
 
@@ -332,9 +557,139 @@ impl FromStr for Manifest {
     }
 }
 
-fn read_config(config_path: &Path) -> Result<Config, failure::Error> {
+/// Renders a `toml::de::Error` as an annotated snippet of `source`, with a caret under
+/// the offending line, similar to how Cargo reports malformed manifests.
+fn located_toml_error(source: &str, config_path: &Path, err: &toml::de::Error) -> failure::Error {
+    let (line, col) = match err.line_col() {
+        Some(line_col) => line_col,
+        None => return failure::format_err!("{} {}", config_path.display(), err),
+    };
+    let line_number = line + 1;
+    let gutter_width = line_number.to_string().len();
+    let line_text = source.lines().nth(line).unwrap_or("");
+
+    failure::format_err!(
+        "{warn} failed to parse {path} at line {line}, column {col}\n{pad} |\n{line} | {text}\n{pad} | {caret}^\n{err}",
+        warn = emoji::WARN,
+        path = config_path.display(),
+        line = line_number,
+        col = col + 1,
+        pad = " ".repeat(gutter_width),
+        text = line_text,
+        caret = " ".repeat(col),
+        err = err,
+    )
+}
+
+/// Walks up from `config_path`'s directory looking for a root `wrangler.toml` whose
+/// `[workspace]` table lists `config_path`'s directory as a member.
+fn find_workspace_root(config_path: &Path) -> Option<(PathBuf, Workspace)> {
+    let member_dir = config_path.parent()?.canonicalize().ok()?;
+    let mut ancestor = member_dir.parent();
+
+    while let Some(dir) = ancestor {
+        let candidate = dir.join("wrangler.toml");
+        if candidate.is_file() {
+            if let Ok(content) = fs::read_to_string(&candidate) {
+                if let Ok(raw) = toml::from_str::<RawManifest>(&content) {
+                    if let Some(workspace) = raw.workspace {
+                        if workspace.contains_member(dir, &member_dir) {
+                            return Some((dir.to_path_buf(), workspace));
+                        }
+                    }
+                }
+            }
+        }
+        ancestor = dir.parent();
+    }
+
+    None
+}
+
+/// Resolves `environment`'s `extends` chain, overlaying each ancestor's unset fields
+/// onto `environment` (the child's own fields always win), and bails with the cycle
+/// path if `extends` loops back on itself.
+fn resolve_environment_extends(
+    name: &str,
+    environment: &Environment,
+    table: &HashMap<String, Environment>,
+    path: &mut Vec<String>,
+) -> Result<Environment, failure::Error> {
+    if path.iter().any(|visited| visited == name) {
+        path.push(name.to_string());
+        failure::bail!(
+            "{} environment \"{}\" extends itself: {}",
+            emoji::WARN,
+            name,
+            path.join(" -> ")
+        );
+    }
+    path.push(name.to_string());
+
+    let resolved = match &environment.extends {
+        Some(parent_name) => {
+            let parent = table.get(parent_name).ok_or_else(|| {
+                failure::format_err!(
+                    "{} environment \"{}\" extends unknown environment \"{}\"",
+                    emoji::WARN,
+                    name,
+                    parent_name
+                )
+            })?;
+            let parent = resolve_environment_extends(parent_name, parent, table, path)?;
+            merge_environment(environment.clone(), parent)
+        }
+        None => environment.clone(),
+    };
+
+    Ok(resolved)
+}
+
+/// Overlays `parent` onto every field `child` left unset. `child`'s own fields always
+/// win; `extends` itself is dropped since the chain is already resolved.
+fn merge_environment(child: Environment, parent: Environment) -> Environment {
+    Environment {
+        name: child.name.or(parent.name),
+        account_id: child.account_id.or(parent.account_id),
+        workers_dev: child.workers_dev.or(parent.workers_dev),
+        route: child.route.or(parent.route),
+        routes: child.routes.or(parent.routes),
+        zone_id: child.zone_id.or(parent.zone_id),
+        webpack_config: child.webpack_config.or(parent.webpack_config),
+        // now opt-in: a child only inherits kv_namespaces by explicitly extending a
+        // parent that defines them, unlike the top-level manifest -> environment axis.
+        kv_namespaces: child.kv_namespaces.or(parent.kv_namespaces),
+        site: child.site.or(parent.site),
+        extends: None,
+    }
+}
+
+/// Builds the final config by layering sources from lowest to highest precedence,
+/// following Cargo's hierarchical config discovery:
+///
+/// 1. the user-global config (`~/.wrangler/config/default.toml`), if present
+/// 2. any `wrangler.toml` found between the project's repo root and its own directory,
+///    furthest first, so nearer directories win
+/// 3. the project's own `wrangler.toml`
+/// 4. `CF`-prefixed environment variables, which always win
+///
+/// `exclude_dir` is the directory of this project's workspace root (if any) -- its
+/// `wrangler.toml` is excluded from this merge since its fields are inherited
+/// explicitly through `[workspace.shared]` (see `find_workspace_root`), not implicitly
+/// via the generic ancestor walk.
+fn read_config(config_path: &Path, exclude_dir: Option<&Path>) -> Result<Config, failure::Error> {
     let mut config = Config::new();
 
+    if let Some(global_config) = global_config_path() {
+        if global_config.is_file() {
+            config.merge(File::from(global_config))?;
+        }
+    }
+
+    for ancestor_config in ancestor_configs(config_path, exclude_dir) {
+        config.merge(File::from(ancestor_config))?;
+    }
+
     let config_str = config_path
         .to_str()
         .expect("project config path should be a string");
@@ -346,6 +701,56 @@ fn read_config(config_path: &Path) -> Result<Config, failure::Error> {
     Ok(config)
 }
 
+/// The user-global config file, shared across every project on this machine.
+fn global_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".wrangler").join("config").join("default.toml"))
+}
+
+/// `wrangler.toml` files in directories between `config_path`'s project root
+/// (inclusive) and its own directory (exclusive -- that one is merged separately),
+/// ordered furthest-from-the-project first so nearer directories win when merged.
+///
+/// The walk is bounded by [`find_project_root`] rather than running to the filesystem
+/// root: an unrelated `wrangler.toml` sitting in some unrelated ancestor directory
+/// (e.g. `$HOME`) must never silently leak its `account_id`/`zone_id` into this
+/// project. `exclude_dir`, when given, is skipped even if it falls within bounds.
+fn ancestor_configs(config_path: &Path, exclude_dir: Option<&Path>) -> Vec<PathBuf> {
+    let project_dir = match config_path.parent().and_then(|dir| dir.canonicalize().ok()) {
+        Some(dir) => dir,
+        None => return Vec::new(),
+    };
+
+    let project_root = match find_project_root(&project_dir) {
+        Some(root) if root != project_dir => root,
+        _ => return Vec::new(),
+    };
+
+    let mut ancestor_configs = Vec::new();
+    for dir in project_dir.ancestors().skip(1) {
+        if exclude_dir != Some(dir) {
+            let candidate = dir.join("wrangler.toml");
+            if candidate.is_file() {
+                ancestor_configs.push(candidate);
+            }
+        }
+        if dir == project_root {
+            break;
+        }
+    }
+
+    ancestor_configs.reverse();
+    ancestor_configs
+}
+
+/// The nearest directory at or above `dir` that looks like a project/repo root, i.e.
+/// contains a `.git` entry. Bounds [`ancestor_configs`] so hierarchical config
+/// discovery stays within the current project instead of walking to `/`.
+fn find_project_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
 fn check_for_duplicate_names(manifest: &Manifest) -> Result<(), failure::Error> {
     let mut names: HashSet<String> = HashSet::new();
     let mut duplicate_names: HashSet<String> = HashSet::new();
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Local, per-project state that lives alongside `wrangler.toml` but isn't part of it:
+/// currently just the active environment, so `--env` doesn't need to be repeated on
+/// every command in a multi-environment project. Modeled on tools like Starship that
+/// read a small `current-context`-style file to know what's "active".
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct State {
+    #[serde(rename = "current-environment")]
+    pub current_environment: Option<String>,
+}
+
+impl State {
+    fn path(project_root: &Path) -> PathBuf {
+        project_root.join(".wrangler").join("state.toml")
+    }
+
+    pub fn read(project_root: &Path) -> Result<Self, failure::Error> {
+        let path = Self::path(project_root);
+        if !path.is_file() {
+            return Ok(State::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn set_current_environment(
+        project_root: &Path,
+        environment_name: &str,
+    ) -> Result<(), failure::Error> {
+        let mut state = Self::read(project_root)?;
+        state.current_environment = Some(environment_name.to_string());
+        state.write(project_root)
+    }
+
+    pub fn clear_current_environment(project_root: &Path) -> Result<(), failure::Error> {
+        let mut state = Self::read(project_root)?;
+        state.current_environment = None;
+        state.write(project_root)
+    }
+
+    fn write(&self, project_root: &Path) -> Result<(), failure::Error> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+}
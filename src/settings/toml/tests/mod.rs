@@ -133,6 +133,354 @@ fn it_uses_env_name_when_provided() {
     assert_eq!(manifest.worker_name(Some(TEST_ENV_NAME)), custom_env_name);
 }
 
+#[test]
+fn it_warns_on_deprecated_route_field() {
+    let dir = env::temp_dir().join("wrangler-toml-deprecated-route-test");
+    fs::create_dir_all(&dir).unwrap();
+    let toml_path = dir.join("wrangler.toml");
+    fs::write(
+        &toml_path,
+        r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+        route = "example.com/*"
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&toml_path).unwrap();
+
+    assert!(manifest
+        .diagnostics
+        .iter()
+        .any(|d| d.level == crate::settings::toml::diagnostics::Level::Deprecation
+            && d.field_path == vec!["route".to_string()]));
+}
+
+#[test]
+fn it_resolves_diagnostics_format_from_env() {
+    env::set_var("CF_DIAGNOSTICS_FORMAT", "json");
+    assert_eq!(DiagnosticsFormat::from_env(), DiagnosticsFormat::Json);
+
+    env::set_var("CF_DIAGNOSTICS_FORMAT", "pretty");
+    assert_eq!(DiagnosticsFormat::from_env(), DiagnosticsFormat::Pretty);
+
+    env::remove_var("CF_DIAGNOSTICS_FORMAT");
+    assert_eq!(DiagnosticsFormat::from_env(), DiagnosticsFormat::Pretty);
+}
+
+#[test]
+fn it_locates_toml_parse_errors() {
+    let dir = env::temp_dir().join("wrangler-toml-parse-error-test");
+    fs::create_dir_all(&dir).unwrap();
+    let toml_path = dir.join("wrangler.toml");
+    fs::write(
+        &toml_path,
+        r#"
+        name = "worker"
+        type = "webpack"
+        account_id = 12345
+        "#,
+    )
+    .unwrap();
+
+    let error = Manifest::new(&toml_path).unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.contains("line 4"));
+    assert!(message.contains("account_id = 12345"));
+}
+
+#[test]
+fn it_inherits_account_id_from_workspace() {
+    let root = env::temp_dir().join("wrangler-toml-workspace-test");
+    let member_dir = root.join("workers").join("api");
+    fs::create_dir_all(&member_dir).unwrap();
+
+    fs::write(
+        root.join("wrangler.toml"),
+        r#"
+        name = "root"
+        type = "webpack"
+        account_id = "rootaccountaccountaccountaccount"
+
+        [workspace]
+        members = ["workers/*"]
+
+        [workspace.shared]
+        account_id = "sharedaccountaccountaccountaccnt"
+        "#,
+    )
+    .unwrap();
+
+    fs::write(
+        member_dir.join("wrangler.toml"),
+        r#"
+        name = "api"
+        type = "webpack"
+
+        [account_id]
+        workspace = true
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&member_dir.join("wrangler.toml")).unwrap();
+
+    assert_eq!(manifest.account_id, "sharedaccountaccountaccountaccnt");
+}
+
+#[test]
+fn it_extends_sibling_environment() {
+    let toml = r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+
+        [env.base]
+        zone_id = "basezonebasezonebasezonebasezone"
+        kv-namespaces = [
+            { binding = "KV", id = "basekvbasekvbasekvbasekvbasekv1" }
+        ]
+
+        [env.preview]
+        extends = "base"
+        "#;
+
+    let manifest = Manifest::from_str(toml).unwrap();
+    let preview = manifest.get_environment(Some("preview")).unwrap().unwrap();
+
+    assert_eq!(
+        preview.zone_id,
+        Some("basezonebasezonebasezonebasezone".to_string())
+    );
+    assert!(preview.kv_namespaces.is_some());
+}
+
+#[test]
+fn it_detects_extends_cycles() {
+    let toml = r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+
+        [env.a]
+        extends = "b"
+
+        [env.b]
+        extends = "a"
+        "#;
+
+    let manifest = Manifest::from_str(toml).unwrap();
+
+    let error = manifest.get_environment(Some("a")).unwrap_err();
+    assert!(error.to_string().contains("a -> b -> a"));
+}
+
+#[test]
+fn it_does_not_read_active_environment_without_a_project_root() {
+    // A manifest built via `FromStr` (as `generate` does) never has `project_root`
+    // set, so it must not fall back to an active environment that happens to be
+    // persisted relative to the process's current directory.
+    let toml = r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+        "#;
+
+    let manifest = Manifest::from_str(toml).unwrap();
+
+    assert_eq!(manifest.active_environment(), None);
+    assert_eq!(manifest.worker_name(None), "worker");
+}
+
+#[test]
+fn it_defaults_env_to_active_environment() {
+    let dir = env::temp_dir().join("wrangler-toml-active-environment-test");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("wrangler.toml"),
+        r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+
+        [env.production]
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&dir.join("wrangler.toml")).unwrap();
+    manifest.set_active_environment("production").unwrap();
+
+    let manifest = Manifest::new(&dir.join("wrangler.toml")).unwrap();
+    assert_eq!(manifest.worker_name(None), "worker-production");
+    assert_eq!(manifest.active_environment(), Some("production".to_string()));
+
+    manifest.clear_active_environment().unwrap();
+    let manifest = Manifest::new(&dir.join("wrangler.toml")).unwrap();
+    assert_eq!(manifest.active_environment(), None);
+}
+
+#[test]
+fn it_handles_a_stale_active_environment_consistently() {
+    let dir = env::temp_dir().join("wrangler-toml-stale-active-environment-test");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("wrangler.toml"),
+        r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+
+        [env.production]
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&dir.join("wrangler.toml")).unwrap();
+    manifest.set_active_environment("production").unwrap();
+
+    // Rewrite the manifest without that environment -- `.wrangler/state.toml` still
+    // points at it.
+    fs::write(
+        dir.join("wrangler.toml"),
+        r#"
+        name = "worker"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+        "#,
+    )
+    .unwrap();
+    let manifest = Manifest::new(&dir.join("wrangler.toml")).unwrap();
+
+    // `worker_name` degrades gracefully to the top-level name...
+    assert_eq!(manifest.worker_name(None), "worker");
+    // ...while `get_target`/`deploy_config`, which actually need the resolved
+    // environment to build a deploy target, surface the same resolution failure
+    // instead of silently building one from the wrong config.
+    assert!(manifest.get_target(None).is_err());
+    assert!(manifest.deploy_config(None).is_err());
+
+    manifest.clear_active_environment().unwrap();
+}
+
+#[test]
+fn it_inherits_zone_id_from_ancestor_config() {
+    let root = env::temp_dir().join("wrangler-toml-ancestor-config-test");
+    let project_dir = root.join("project");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+
+    fs::write(
+        root.join("wrangler.toml"),
+        r#"
+        name = "ancestor"
+        type = "webpack"
+        zone_id = "ancestorzoneancestorzoneancestor"
+        "#,
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("wrangler.toml"),
+        r#"
+        name = "project"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&project_dir.join("wrangler.toml")).unwrap();
+
+    assert_eq!(
+        manifest.zone_id,
+        Some("ancestorzoneancestorzoneancestor".to_string())
+    );
+    assert_eq!(manifest.name, "project");
+}
+
+#[test]
+fn it_does_not_inherit_config_outside_the_project_root() {
+    let root = env::temp_dir().join("wrangler-toml-outside-project-root-test");
+    let project_dir = root.join("unrelated-project");
+    fs::create_dir_all(&project_dir).unwrap();
+    // No `.git` at `root`, so it never bounds the walk -- `root`'s `wrangler.toml`
+    // must not be treated as this project's config.
+    fs::create_dir_all(project_dir.join(".git")).unwrap();
+
+    fs::write(
+        root.join("wrangler.toml"),
+        r#"
+        name = "somebody-elses-project"
+        type = "webpack"
+        zone_id = "outsidezoneoutsidezoneoutsidezon"
+        "#,
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("wrangler.toml"),
+        r#"
+        name = "project"
+        type = "webpack"
+        account_id = "badc0ffee0ddf00dbadc0ffee0ddf00d"
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&project_dir.join("wrangler.toml")).unwrap();
+
+    assert_eq!(manifest.zone_id, None);
+}
+
+#[test]
+fn it_does_not_leak_workspace_root_fields_via_ancestor_merge() {
+    let root = env::temp_dir().join("wrangler-toml-workspace-ancestor-collision-test");
+    let member_dir = root.join("workers").join("api");
+    fs::create_dir_all(&member_dir).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+
+    fs::write(
+        root.join("wrangler.toml"),
+        r#"
+        name = "root"
+        type = "webpack"
+        account_id = "rootaccountaccountaccountaccount"
+        zone_id = "rootzonerootzonerootzonerootzone"
+
+        [workspace]
+        members = ["workers/*"]
+
+        [workspace.shared]
+        account_id = "sharedaccountaccountaccountaccnt"
+        "#,
+    )
+    .unwrap();
+
+    fs::write(
+        member_dir.join("wrangler.toml"),
+        r#"
+        name = "api"
+        type = "webpack"
+
+        [account_id]
+        workspace = true
+        "#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::new(&member_dir.join("wrangler.toml")).unwrap();
+
+    assert_eq!(manifest.account_id, "sharedaccountaccountaccountaccnt");
+    // Only the explicitly shared `account_id` is inherited -- the root's own
+    // `zone_id`, which was never declared under `[workspace.shared]`, must not leak
+    // in just because the root happens to be an ancestor directory.
+    assert_eq!(manifest.zone_id, None);
+}
+
 fn base_fixture_path() -> PathBuf {
     let current_dir = env::current_dir().unwrap();
 
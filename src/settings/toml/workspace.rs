@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::toml::kv_namespace::KvNamespace;
+
+/// The `[workspace]` table in a root `wrangler.toml`, declaring which directories
+/// contain member Workers and what configuration they can inherit.
+///
+/// Mirrors Cargo's `[workspace]` manifest: member manifests opt into pulling a field
+/// from `shared` with e.g. `account_id.workspace = true` instead of repeating it.
+/// `zone_id` is the exception: a member that leaves it unset always takes
+/// `shared.zone_id`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Workspace {
+    /// Glob patterns, relative to this manifest, identifying member directories.
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub shared: WorkspaceShared,
+}
+
+/// Fields declared under `[workspace.shared]` that members may inherit.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct WorkspaceShared {
+    pub account_id: Option<String>,
+    #[serde(default, with = "serde_with::rust::string_empty_as_none")]
+    pub zone_id: Option<String>,
+    #[serde(rename = "kv-namespaces")]
+    pub kv_namespaces: Option<Vec<KvNamespace>>,
+}
+
+impl Workspace {
+    /// Expands `members` into the directories they match, relative to `workspace_root`.
+    pub fn member_dirs(&self, workspace_root: &Path) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for pattern in &self.members {
+            let full_pattern = workspace_root.join(pattern).to_string_lossy().into_owned();
+            match glob::glob(&full_pattern) {
+                Ok(paths) => dirs.extend(paths.filter_map(Result::ok).filter(|p| p.is_dir())),
+                Err(e) => log::debug!("invalid workspace member pattern \"{}\": {}", pattern, e),
+            }
+        }
+        dirs
+    }
+
+    /// True if `member_dir` is one of this workspace's declared members.
+    pub fn contains_member(&self, workspace_root: &Path, member_dir: &Path) -> bool {
+        self.member_dirs(workspace_root)
+            .iter()
+            .any(|dir| dir == member_dir)
+    }
+}
+
+/// A field that is either defined directly, or marked `{ workspace = true }` to be
+/// pulled from the enclosing workspace's `[workspace.shared]` table.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MaybeWorkspace<T> {
+    Workspace(WorkspaceFlag),
+    Defined(T),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WorkspaceFlag {
+    pub workspace: bool,
+}
+
+impl<T: Default> Default for MaybeWorkspace<T> {
+    fn default() -> Self {
+        MaybeWorkspace::Defined(T::default())
+    }
+}
+
+impl<T: Clone> MaybeWorkspace<T> {
+    /// Resolves this field to a concrete value, pulling from `shared` when this field
+    /// was written as `{ workspace = true }`.
+    pub fn resolve(&self, field_name: &str, shared: Option<&T>) -> Result<T, failure::Error> {
+        match self {
+            MaybeWorkspace::Defined(value) => Ok(value.clone()),
+            MaybeWorkspace::Workspace(flag) => {
+                if !flag.workspace {
+                    failure::bail!(
+                        "`{}.workspace` must be `true` to inherit from the workspace",
+                        field_name
+                    );
+                }
+                shared.cloned().ok_or_else(|| {
+                    failure::format_err!(
+                        "`{}` is marked `workspace = true`, but no workspace root defines `workspace.shared.{}`",
+                        field_name,
+                        field_name
+                    )
+                })
+            }
+        }
+    }
+}